@@ -1,22 +1,205 @@
 use std::sync::{Arc, RwLock};
 
 use axum::Router;
+use tokio::sync::{broadcast, mpsc};
 
 use crate::documents::DocumentStore;
-use crate::routes::document_routes;
+use crate::error::write_lock;
+use crate::routes::{document_routes, search_routes, task_routes};
+use crate::storage::Storage;
+use crate::tasks::{IndexJob, TaskStatus, TaskStore};
+
+/// How many unconsumed change notifications a lagging poller can fall
+/// behind by before it starts missing them. Pollers only care about the
+/// most recent change to their key, so a lag just means a re-check.
+const CHANGE_CHANNEL_CAPACITY: usize = 256;
 
-#[derive(Default)]
 pub struct AppState {
     pub document_store: DocumentStore,
+    pub task_store: TaskStore,
+    pub index_queue: mpsc::UnboundedSender<IndexJob>,
+    /// Broadcasts the id of every document inserted or updated, so
+    /// `GET /document/{key}/poll` can wait for a specific key to change
+    /// instead of busy-polling `GET /document/{key}`.
+    pub change_notifier: broadcast::Sender<String>,
 }
 
 pub type SharedState = Arc<RwLock<AppState>>;
 
-pub fn build_app() -> Router {
-    let shared_state = SharedState::default();
+/// Builds the app with documents persisted to a SQLite database at
+/// `db_path`. Existing documents (and their indexes) are loaded back into
+/// memory before the server starts serving requests.
+pub fn build_app(db_path: &str) -> Router {
+    let storage = Storage::open(db_path).expect("failed to open document storage");
+    let mut document_store = DocumentStore::with_storage(storage);
+    document_store.load_from_storage();
+
+    let (index_queue, index_jobs) = mpsc::unbounded_channel();
+    let (change_notifier, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+
+    let shared_state: SharedState = Arc::new(RwLock::new(AppState {
+        document_store,
+        task_store: TaskStore::default(),
+        index_queue,
+        change_notifier,
+    }));
+
+    tokio::spawn(run_index_worker(shared_state.clone(), index_jobs));
 
     Router::new()
-        // .route("/search", get(handle_search))
         .merge(document_routes())
+        .merge(search_routes())
+        .merge(task_routes())
         .with_state(shared_state)
 }
+
+/// Drains queued index jobs and applies them one at a time, decoupling
+/// document-write latency from indexing cost.
+async fn run_index_worker(state: SharedState, mut jobs: mpsc::UnboundedReceiver<IndexJob>) {
+    while let Some(job) = jobs.recv().await {
+        process_index_job(&state, job);
+    }
+}
+
+/// Indexes one job's document and marks its task accordingly. A storage
+/// failure here fails just that task rather than taking the worker loop
+/// down with it — `run_index_worker` must keep draining the queue, or
+/// every job already enqueued (and every one enqueued afterward) would be
+/// silently dropped forever. Takes the lock through `write_lock` rather
+/// than a bare `.write().unwrap()` for the same reason: a panic while
+/// some other job or handler holds the lock must not take this worker
+/// down too.
+fn process_index_job(state: &SharedState, job: IndexJob) {
+    let Ok(mut state) = write_lock(state) else {
+        return;
+    };
+    state
+        .task_store
+        .set_status(&job.task_id, TaskStatus::Processing, None);
+
+    let document_id = job.document.id.clone();
+    match state.document_store.insert(job.document) {
+        Ok(()) => {
+            let _ = state.change_notifier.send(document_id);
+            state
+                .task_store
+                .set_status(&job.task_id, TaskStatus::Succeeded, None);
+        }
+        Err(error) => {
+            state
+                .task_store
+                .set_status(&job.task_id, TaskStatus::Failed, Some(error.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Document;
+    use crate::tasks::{Task, TaskKind};
+
+    #[test]
+    fn test_process_index_job_indexes_document_and_marks_task_succeeded() {
+        let (index_queue, _jobs) = mpsc::unbounded_channel();
+        let (change_notifier, _changes) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        let state: SharedState = Arc::new(RwLock::new(AppState {
+            document_store: DocumentStore::default(),
+            task_store: TaskStore::default(),
+            index_queue,
+            change_notifier,
+        }));
+
+        let task = Task::new(TaskKind::CreateDocument);
+        let task_id = task.id.clone();
+        state.write().unwrap().task_store.insert(task);
+
+        let document = Document::new("doc1".to_string(), "hello world".to_string());
+        process_index_job(
+            &state,
+            IndexJob {
+                task_id: task_id.clone(),
+                document,
+            },
+        );
+
+        let state = state.read().unwrap();
+        assert!(state.document_store.get("doc1").is_some());
+        assert_eq!(
+            state.task_store.get(&task_id).unwrap().status,
+            TaskStatus::Succeeded
+        );
+    }
+
+    #[test]
+    fn test_process_index_job_marks_task_failed_on_storage_error_and_keeps_document_unindexed() {
+        use crate::storage::Storage;
+
+        let path = std::env::temp_dir().join(format!(
+            "search-engine-app-test-{}.sqlite",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        std::fs::remove_file(path).ok();
+
+        let storage = Storage::open(path).unwrap();
+        // Pull the table out from under `storage`'s connection via a second
+        // handle, so its next write fails the way a real storage fault
+        // (disk full, permissions, corruption) would.
+        rusqlite::Connection::open(path)
+            .unwrap()
+            .execute("DROP TABLE documents", [])
+            .unwrap();
+
+        let (index_queue, _jobs) = mpsc::unbounded_channel();
+        let (change_notifier, _changes) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        let state: SharedState = Arc::new(RwLock::new(AppState {
+            document_store: DocumentStore::with_storage(storage),
+            task_store: TaskStore::default(),
+            index_queue,
+            change_notifier,
+        }));
+
+        let task = Task::new(TaskKind::CreateDocument);
+        let task_id = task.id.clone();
+        state.write().unwrap().task_store.insert(task);
+
+        let document = Document::new("doc1".to_string(), "hello world".to_string());
+        process_index_job(
+            &state,
+            IndexJob {
+                task_id: task_id.clone(),
+                document,
+            },
+        );
+
+        let state = state.read().unwrap();
+        assert!(state.document_store.get("doc1").is_none());
+        let task = state.task_store.get(&task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert!(task.error.is_some());
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_process_index_job_notifies_change_subscribers() {
+        let (index_queue, _jobs) = mpsc::unbounded_channel();
+        let (change_notifier, mut changes) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        let state: SharedState = Arc::new(RwLock::new(AppState {
+            document_store: DocumentStore::default(),
+            task_store: TaskStore::default(),
+            index_queue,
+            change_notifier,
+        }));
+
+        let task = Task::new(TaskKind::CreateDocument);
+        let task_id = task.id.clone();
+        state.write().unwrap().task_store.insert(task);
+
+        let document = Document::new("doc1".to_string(), "hello world".to_string());
+        process_index_job(&state, IndexJob { task_id, document });
+
+        assert_eq!(changes.try_recv().unwrap(), "doc1");
+    }
+}
@@ -2,6 +2,9 @@ use std::collections::{hash_map::Iter, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use crate::index::{ngram_tokenize, tokenize, PostingIndex};
+use crate::storage::Storage;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Document {
     pub id: String,
@@ -20,14 +23,77 @@ impl Document {
     }
 }
 
+/// Whether `DocumentStore::search` matches whole words or character
+/// n-grams. Fuzzy mode trades precision for typo- and prefix-tolerance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    #[default]
+    Exact,
+    Fuzzy,
+}
+
 #[derive(Default)]
 pub struct DocumentStore {
     documents: HashMap<String, Document>,
+    word_index: PostingIndex,
+    ngram_index: PostingIndex,
+    storage: Option<Storage>,
 }
 
 impl DocumentStore {
-    pub fn insert(&mut self, document: Document) {
+    /// A store backed by SQLite: `insert`/`remove` write through to it, and
+    /// `load_from_storage` rebuilds the in-memory cache and indexes from
+    /// whatever was already persisted.
+    pub fn with_storage(storage: Storage) -> Self {
+        DocumentStore {
+            storage: Some(storage),
+            ..Default::default()
+        }
+    }
+
+    pub fn load_from_storage(&mut self) {
+        let Some(storage) = &self.storage else {
+            return;
+        };
+        let documents = storage
+            .load_all()
+            .expect("failed to load documents from storage");
+
+        for document in documents {
+            self.index(&document);
+            self.documents.insert(document.id.clone(), document);
+        }
+    }
+
+    /// Persists `document` before touching the in-memory cache or indexes,
+    /// so a storage failure leaves both untouched instead of drifting out
+    /// of sync with what's actually on disk.
+    pub fn insert(&mut self, document: Document) -> rusqlite::Result<()> {
+        if let Some(storage) = &self.storage {
+            storage.put(&document)?;
+        }
+        if let Some(old) = self.documents.remove(&document.id) {
+            self.unindex(&old);
+        }
+        self.index(&document);
         self.documents.insert(document.id.clone(), document);
+        Ok(())
+    }
+
+    /// Deletes from storage before touching the in-memory cache or
+    /// indexes, for the same reason `insert` writes through first: a
+    /// storage failure should leave both untouched instead of drifting
+    /// out of sync with what's actually on disk.
+    pub fn remove(&mut self, id: &str) -> rusqlite::Result<Option<Document>> {
+        if let Some(storage) = &self.storage {
+            storage.delete(id)?;
+        }
+        let removed = self.documents.remove(id);
+        if let Some(document) = &removed {
+            self.unindex(document);
+        }
+        Ok(removed)
     }
 
     pub fn get(&self, id: &str) -> Option<&Document> {
@@ -37,29 +103,46 @@ impl DocumentStore {
     pub fn iter(&self) -> Iter<String, Document> {
         self.documents.iter()
     }
-}
 
-// n-grams:
-// split document into words, and calculate n-grams for each word
-// when handling a search request, split query into words and then n-grams. calculate BM25 for each n-gram and add up the score
-
-// to calculate BM25 score:
-// score(document, word) = term_frequency(document, word) * inverse_document_frequency(word)
-//
-// term_frequency_score(document, word) = frequency(word, document) * (k1 + 1) / (frequency(word, document) + k1 * (1 - b + b * document_length / avg_document_length))
-//
-// k1 = 1.5
-// b = 0.75
-//
-// inverse_document_frequency(word) = log((total_documents - document_with_word + 0.5) / (document_with_word + 0.5))
-//
-// we need to calculate the following:
-// - frequency(word, document)
-// - document_length
-// - avg_document_length
-// - total_documents
-// - document_with_word
-//
+    pub fn document_count(&self) -> usize {
+        self.documents.len()
+    }
+
+    fn index(&mut self, document: &Document) {
+        self.word_index
+            .index(&document.id, tokenize(&document.text));
+        self.ngram_index
+            .index(&document.id, ngram_tokenize(&document.text));
+    }
+
+    fn unindex(&mut self, document: &Document) {
+        self.word_index
+            .unindex(&document.id, tokenize(&document.text));
+        self.ngram_index
+            .unindex(&document.id, ngram_tokenize(&document.text));
+    }
+
+    /// Rank documents against `query` by summed BM25 score, descending.
+    /// Query terms that aren't in the index contribute nothing rather than
+    /// excluding the document from the results.
+    pub fn search(&self, query: &str, mode: SearchMode) -> Vec<(String, f64)> {
+        let total_documents = self.document_count();
+        if total_documents == 0 {
+            return Vec::new();
+        }
+
+        let scores = match mode {
+            SearchMode::Exact => self.word_index.search(tokenize(query), total_documents),
+            SearchMode::Fuzzy => self
+                .ngram_index
+                .search(ngram_tokenize(query), total_documents),
+        };
+
+        let mut ranked: Vec<(String, f64)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        ranked
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -107,7 +190,7 @@ mod tests {
             word_count: 2,
         };
 
-        store.insert(doc.clone());
+        store.insert(doc.clone()).unwrap();
 
         let retrieved = store.get("doc1");
         assert!(retrieved.is_some());
@@ -130,14 +213,14 @@ mod tests {
             text: "Original text".to_string(),
             word_count: 2,
         };
-        store.insert(doc1);
+        store.insert(doc1).unwrap();
 
         let doc2 = Document {
             id: "doc1".to_string(),
             text: "Updated text".to_string(),
             word_count: 2,
         };
-        store.insert(doc2);
+        store.insert(doc2).unwrap();
 
         let retrieved = store.get("doc1").unwrap();
         assert_eq!(retrieved.text, "Updated text");
@@ -158,8 +241,8 @@ mod tests {
             word_count: 2,
         };
 
-        store.insert(doc1);
-        store.insert(doc2);
+        store.insert(doc1).unwrap();
+        store.insert(doc2).unwrap();
 
         let documents: Vec<_> = store.iter().collect();
         assert_eq!(documents.len(), 2);
@@ -181,4 +264,127 @@ mod tests {
         assert_eq!(doc.id, cloned.id);
         assert_eq!(doc.text, cloned.text);
     }
+
+    #[test]
+    fn test_search_ranks_matching_documents() {
+        let mut store = DocumentStore::default();
+        store
+            .insert(Document::new(
+                "doc1".to_string(),
+                "the quick brown fox".to_string(),
+            ))
+            .unwrap();
+        store
+            .insert(Document::new(
+                "doc2".to_string(),
+                "the lazy dog sleeps".to_string(),
+            ))
+            .unwrap();
+
+        let results = store.search("fox", SearchMode::Exact);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "doc1");
+        assert!(results[0].1 > 0.0);
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive() {
+        let mut store = DocumentStore::default();
+        store.insert(Document::new("doc1".to_string(), "Hello world".to_string())).unwrap();
+
+        let results = store.search("hello", SearchMode::Exact);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_search_unknown_term_returns_no_results() {
+        let mut store = DocumentStore::default();
+        store.insert(Document::new("doc1".to_string(), "hello world".to_string())).unwrap();
+
+        let results = store.search("nonexistent", SearchMode::Exact);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_empty_corpus() {
+        let store = DocumentStore::default();
+        let results = store.search("anything", SearchMode::Exact);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_removes_stale_postings_on_update() {
+        let mut store = DocumentStore::default();
+        store.insert(Document::new("doc1".to_string(), "apple banana".to_string())).unwrap();
+        store.insert(Document::new("doc1".to_string(), "cherry".to_string())).unwrap();
+
+        assert!(store.search("apple", SearchMode::Exact).is_empty());
+        assert_eq!(store.search("cherry", SearchMode::Exact)[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_fuzzy_search_matches_typos_via_shared_ngrams() {
+        let mut store = DocumentStore::default();
+        store
+            .insert(Document::new(
+                "doc1".to_string(),
+                "the search engine indexes documents".to_string(),
+            ))
+            .unwrap();
+
+        // "serach" is a typo of "search" but shares the padded boundary
+        // trigrams "$se" and "ch$" with it.
+        let results = store.search("serach", SearchMode::Fuzzy);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_exact_search_does_not_match_typos() {
+        let mut store = DocumentStore::default();
+        store.insert(Document::new("doc1".to_string(), "search engine".to_string())).unwrap();
+
+        let results = store.search("serach", SearchMode::Exact);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_load_from_storage_rebuilds_documents_and_index() {
+        let storage = Storage::open(":memory:").unwrap();
+        storage
+            .put(&Document::new("doc1".to_string(), "hello world".to_string()))
+            .unwrap();
+
+        let mut store = DocumentStore::with_storage(storage);
+        store.load_from_storage();
+
+        assert_eq!(store.document_count(), 1);
+        assert_eq!(store.search("hello", SearchMode::Exact)[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_insert_and_remove_write_through_to_storage() {
+        let path = std::env::temp_dir().join(format!(
+            "search-engine-test-{}.sqlite",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        std::fs::remove_file(path).ok();
+
+        {
+            let mut store = DocumentStore::with_storage(Storage::open(path).unwrap());
+            store.insert(Document::new("doc1".to_string(), "hello world".to_string())).unwrap();
+            store.insert(Document::new("doc2".to_string(), "still here".to_string())).unwrap();
+            store.remove("doc1").unwrap();
+        }
+
+        // Reopen against the same file to confirm the writes landed.
+        let mut reloaded = DocumentStore::with_storage(Storage::open(path).unwrap());
+        reloaded.load_from_storage();
+        assert_eq!(reloaded.document_count(), 1);
+        assert!(reloaded.get("doc2").is_some());
+
+        std::fs::remove_file(path).ok();
+    }
 }
@@ -0,0 +1,129 @@
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+/// A machine-readable API error. `IntoResponse` serializes it as
+/// `{ "code": ..., "message": ..., "type": "invalid_request" | "internal" }`
+/// with the matching HTTP status, so clients can branch on `code` instead
+/// of parsing prose.
+#[derive(Debug)]
+pub enum ApiError {
+    DocumentNotFound,
+    TaskNotFound,
+    InvalidInput(String),
+    Internal(String),
+}
+
+#[derive(Serialize)]
+struct ApiErrorBody {
+    code: &'static str,
+    message: String,
+    #[serde(rename = "type")]
+    error_type: &'static str,
+}
+
+impl ApiError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ApiError::DocumentNotFound | ApiError::TaskNotFound => StatusCode::NOT_FOUND,
+            ApiError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            ApiError::DocumentNotFound => "document_not_found",
+            ApiError::TaskNotFound => "task_not_found",
+            ApiError::InvalidInput(_) => "invalid_input",
+            ApiError::Internal(_) => "internal_error",
+        }
+    }
+
+    fn error_type(&self) -> &'static str {
+        match self {
+            ApiError::Internal(_) => "internal",
+            _ => "invalid_request",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ApiError::DocumentNotFound => "document not found".to_string(),
+            ApiError::TaskNotFound => "task not found".to_string(),
+            ApiError::InvalidInput(message) => message.clone(),
+            ApiError::Internal(message) => message.clone(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ApiErrorBody {
+            code: self.code(),
+            message: self.message(),
+            error_type: self.error_type(),
+        };
+
+        (self.status(), Json(body)).into_response()
+    }
+}
+
+/// Acquire a read lock, mapping poisoning (a panic in some other handler
+/// while holding the lock) to an `Internal` error instead of taking this
+/// handler down too.
+pub fn read_lock<T>(lock: &RwLock<T>) -> Result<RwLockReadGuard<'_, T>, ApiError> {
+    lock.read()
+        .map_err(|_| ApiError::Internal("state lock poisoned".to_string()))
+}
+
+/// As `read_lock`, for the write half.
+pub fn write_lock<T>(lock: &RwLock<T>) -> Result<RwLockWriteGuard<'_, T>, ApiError> {
+    lock.write()
+        .map_err(|_| ApiError::Internal("state lock poisoned".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[tokio::test]
+    async fn test_document_not_found_serializes_expected_body() {
+        let response = ApiError::DocumentNotFound.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "document_not_found");
+        assert_eq!(json["type"], "invalid_request");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_input_serializes_expected_body() {
+        let response = ApiError::InvalidInput("document text must not be empty".to_string())
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "invalid_input");
+        assert_eq!(json["type"], "invalid_request");
+        assert_eq!(json["message"], "document text must not be empty");
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_has_internal_type() {
+        let response = ApiError::Internal("boom".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "internal_error");
+        assert_eq!(json["type"], "internal");
+        assert_eq!(json["message"], "boom");
+    }
+}
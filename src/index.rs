@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+// BM25 tuning parameters.
+const K1: f64 = 1.5;
+const B: f64 = 0.75;
+
+const NGRAM_SIZE: usize = 3;
+const NGRAM_SENTINEL: char = '$';
+
+/// An inverted index over some tokenization of document text: term ->
+/// (document id -> occurrences), plus the per-document and corpus-wide
+/// length bookkeeping BM25 needs.
+#[derive(Default)]
+pub struct PostingIndex {
+    postings: HashMap<String, HashMap<String, usize>>,
+    lengths: HashMap<String, usize>,
+    total_length: usize,
+}
+
+impl PostingIndex {
+    pub fn index(&mut self, doc_id: &str, tokens: impl Iterator<Item = String>) {
+        let mut length = 0;
+        for token in tokens {
+            length += 1;
+            *self
+                .postings
+                .entry(token)
+                .or_default()
+                .entry(doc_id.to_string())
+                .or_insert(0) += 1;
+        }
+        self.total_length += length;
+        self.lengths.insert(doc_id.to_string(), length);
+    }
+
+    pub fn unindex(&mut self, doc_id: &str, tokens: impl Iterator<Item = String>) {
+        if let Some(length) = self.lengths.remove(doc_id) {
+            self.total_length -= length;
+        }
+        for token in tokens {
+            if let Some(postings) = self.postings.get_mut(&token) {
+                postings.remove(doc_id);
+                if postings.is_empty() {
+                    self.postings.remove(&token);
+                }
+            }
+        }
+    }
+
+    fn average_length(&self) -> f64 {
+        if self.lengths.is_empty() {
+            0.0
+        } else {
+            self.total_length as f64 / self.lengths.len() as f64
+        }
+    }
+
+    /// Sum BM25 scores for `terms` across every document each term appears
+    /// in. Terms absent from the index are skipped rather than zeroing out
+    /// the whole query.
+    pub fn search(
+        &self,
+        terms: impl Iterator<Item = String>,
+        total_documents: usize,
+    ) -> HashMap<String, f64> {
+        let avg_length = self.average_length();
+        let mut scores = HashMap::new();
+
+        for term in terms {
+            let Some(postings) = self.postings.get(&term) else {
+                continue;
+            };
+            let idf = inverse_document_frequency(total_documents, postings.len());
+
+            for (doc_id, &term_frequency) in postings {
+                let document_length = *self.lengths.get(doc_id).unwrap_or(&0);
+                let score =
+                    idf * term_frequency_score(term_frequency, document_length, avg_length);
+                *scores.entry(doc_id.clone()).or_insert(0.0) += score;
+            }
+        }
+
+        scores
+    }
+}
+
+fn inverse_document_frequency(total_documents: usize, documents_with_term: usize) -> f64 {
+    ((total_documents as f64 - documents_with_term as f64 + 0.5)
+        / (documents_with_term as f64 + 0.5)
+        + 1.0)
+        .ln()
+}
+
+fn term_frequency_score(
+    term_frequency: usize,
+    document_length: usize,
+    avg_document_length: f64,
+) -> f64 {
+    let term_frequency = term_frequency as f64;
+    let length_norm = if avg_document_length > 0.0 {
+        1.0 - B + B * (document_length as f64 / avg_document_length)
+    } else {
+        1.0 - B
+    };
+
+    term_frequency * (K1 + 1.0) / (term_frequency + K1 * length_norm)
+}
+
+/// Lowercased whole-word tokens.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace().map(|word| word.to_lowercase())
+}
+
+/// Overlapping character n-grams of a single (already lowercased) word,
+/// padded with a sentinel so short words and prefixes still produce at
+/// least one gram, e.g. "search" -> "$se", "sea", "ear", "arc", "rch", "ch$".
+pub fn ngrams(word: &str) -> Vec<String> {
+    let padded: Vec<char> = std::iter::once(NGRAM_SENTINEL)
+        .chain(word.chars())
+        .chain(std::iter::once(NGRAM_SENTINEL))
+        .collect();
+
+    if padded.len() <= NGRAM_SIZE {
+        return vec![padded.into_iter().collect()];
+    }
+
+    padded
+        .windows(NGRAM_SIZE)
+        .map(|window| window.iter().collect())
+        .collect()
+}
+
+/// n-gram tokens for every word in `text`.
+pub fn ngram_tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    tokenize(text).flat_map(|word| ngrams(&word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ngrams_of_a_whole_word() {
+        assert_eq!(
+            ngrams("search"),
+            vec!["$se", "sea", "ear", "arc", "rch", "ch$"]
+        );
+    }
+
+    #[test]
+    fn test_ngrams_of_a_short_word_still_yields_a_gram() {
+        assert_eq!(ngrams("a"), vec!["$a$"]);
+    }
+
+    #[test]
+    fn test_posting_index_unindex_removes_all_traces() {
+        let mut index = PostingIndex::default();
+        index.index("doc1", tokenize("apple banana"));
+        index.unindex("doc1", tokenize("apple banana"));
+
+        let scores = index.search(tokenize("apple"), 0);
+        assert!(scores.is_empty());
+    }
+}
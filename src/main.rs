@@ -2,13 +2,20 @@ use app::build_app;
 
 mod app;
 mod documents;
+mod error;
+mod index;
 mod routes;
+mod storage;
+mod tasks;
+
+const DEFAULT_DB_PATH: &str = "documents.db";
 
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
 
-    let app = build_app();
+    let db_path = std::env::var("DOCUMENTS_DB_PATH").unwrap_or_else(|_| DEFAULT_DB_PATH.to_string());
+    let app = build_app(&db_path);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await.unwrap();
     axum::serve(listener, app).await.unwrap();
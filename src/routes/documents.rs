@@ -1,61 +1,202 @@
+use std::time::Duration;
+
 use axum::routing::{get, post};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
+    response::{IntoResponse, Response},
     Json, Router,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::time::{self, Instant};
 use uuid::Uuid;
 
-use crate::{app::SharedState, documents::Document};
+use crate::{
+    app::SharedState,
+    documents::Document,
+    error::{read_lock, write_lock, ApiError},
+    tasks::{IndexJob, Task, TaskKind},
+};
+
+/// Used when the caller's `/document/{key}/poll` request doesn't specify
+/// `timeout_ms`.
+const DEFAULT_POLL_TIMEOUT_MS: u64 = 30_000;
+/// Caps how long a single poll request can hold a connection open.
+const MAX_POLL_TIMEOUT_MS: u64 = 60_000;
 
 #[derive(Deserialize)]
 pub struct DocumentInput {
     text: String,
 }
 
+#[derive(Deserialize)]
+pub struct BatchDocumentInput {
+    id: Option<String>,
+    text: String,
+}
+
+#[derive(Serialize)]
+pub struct BatchGetResult {
+    id: String,
+    document: Option<Document>,
+}
+
+#[derive(Serialize)]
+pub struct BatchDeleteResult {
+    id: String,
+    deleted: bool,
+}
+
+#[derive(Deserialize)]
+pub struct PollParams {
+    timeout_ms: Option<u64>,
+}
+
+/// Rejects text that's empty (or only whitespace), since such a document
+/// would never be findable through either index.
+fn require_non_empty_text(text: &str) -> Result<(), ApiError> {
+    if text.trim().is_empty() {
+        return Err(ApiError::InvalidInput(
+            "document text must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Enqueues indexing rather than doing it inline, so write latency isn't
+/// coupled to indexing cost. The caller polls `GET /tasks/{uid}` to learn
+/// when the document becomes searchable.
 pub async fn handle_create_document(
     State(state): State<SharedState>,
     Json(document): Json<DocumentInput>,
-) -> Result<Json<Document>, StatusCode> {
+) -> Result<(StatusCode, Json<Task>), ApiError> {
+    require_non_empty_text(&document.text)?;
+
     let id = Uuid::new_v4().to_string();
     let document = Document::new(id, document.text);
+    let task = Task::new(TaskKind::CreateDocument);
 
-    state
-        .write()
-        .unwrap()
-        .document_store
-        .insert(document.clone());
+    let mut state = write_lock(&state)?;
+    state.task_store.insert(task.clone());
+    let _ = state.index_queue.send(IndexJob {
+        task_id: task.id.clone(),
+        document,
+    });
 
-    Ok(Json(document))
+    Ok((StatusCode::ACCEPTED, Json(task)))
 }
 
 pub async fn handle_update_document(
     Path(key): Path<String>,
     State(state): State<SharedState>,
     Json(document): Json<DocumentInput>,
-) {
+) -> Result<(StatusCode, Json<Task>), ApiError> {
+    require_non_empty_text(&document.text)?;
+
     let document = Document::new(key, document.text);
+    let task = Task::new(TaskKind::UpdateDocument);
+
+    let mut state = write_lock(&state)?;
+    state.task_store.insert(task.clone());
+    let _ = state.index_queue.send(IndexJob {
+        task_id: task.id.clone(),
+        document,
+    });
 
-    state.write().unwrap().document_store.insert(document);
+    Ok((StatusCode::ACCEPTED, Json(task)))
+}
+
+pub async fn handle_delete_document(
+    Path(key): Path<String>,
+    State(state): State<SharedState>,
+) -> Result<StatusCode, ApiError> {
+    let removed = write_lock(&state)?
+        .document_store
+        .remove(&key)
+        .map_err(|error| ApiError::Internal(error.to_string()))?;
+
+    match removed {
+        Some(_) => Ok(StatusCode::NO_CONTENT),
+        None => Err(ApiError::DocumentNotFound),
+    }
 }
 
 pub async fn handle_get_document(
     Path(key): Path<String>,
     State(state): State<SharedState>,
-) -> Result<Json<Document>, StatusCode> {
-    match state.read().unwrap().document_store.get(&key) {
+) -> Result<Json<Document>, ApiError> {
+    match read_lock(&state)?.document_store.get(&key) {
         Some(document) => Ok(Json(document.clone())),
-        None => Err(StatusCode::NOT_FOUND),
+        None => Err(ApiError::DocumentNotFound),
+    }
+}
+
+/// Returns `key`'s document as soon as it exists: at once if it is already
+/// stored, otherwise when it is created, or `304 Not Modified` once
+/// `timeout_ms` elapses without it appearing — so a caller doesn't have to
+/// busy-poll `GET /document/{key}`. A document that already exists is
+/// returned as it is; a later update to it is not waited for.
+///
+/// Subscribes before checking whether the document already exists, both
+/// under the same read lock: checking first could miss a change that
+/// lands between the check and the subscribe (notably the create→poll
+/// race, where the background worker may index the document before the
+/// poll subscribes), and subscribing first without checking would block
+/// the full timeout for a document that was already there.
+pub async fn handle_poll_document(
+    Path(key): Path<String>,
+    Query(params): Query<PollParams>,
+    State(state): State<SharedState>,
+) -> Result<Response, ApiError> {
+    let timeout_ms = params
+        .timeout_ms
+        .unwrap_or(DEFAULT_POLL_TIMEOUT_MS)
+        .min(MAX_POLL_TIMEOUT_MS);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+    let mut changes = {
+        let state = read_lock(&state)?;
+        let changes = state.change_notifier.subscribe();
+        if let Some(document) = state.document_store.get(&key) {
+            return Ok(Json(document.clone()).into_response());
+        }
+        changes
+    };
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(StatusCode::NOT_MODIFIED.into_response());
+        }
+
+        match time::timeout(remaining, changes.recv()).await {
+            Ok(Ok(changed_id)) if changed_id == key => {
+                return match read_lock(&state)?.document_store.get(&key) {
+                    Some(document) => Ok(Json(document.clone()).into_response()),
+                    None => Ok(StatusCode::NOT_MODIFIED.into_response()),
+                };
+            }
+            Ok(Ok(_)) => continue,
+            Ok(Err(broadcast::error::RecvError::Lagged(_))) => {
+                // A dropped notification may have been for `key`, so check
+                // the store directly before waiting again.
+                if let Some(document) = read_lock(&state)?.document_store.get(&key) {
+                    return Ok(Json(document.clone()).into_response());
+                }
+            }
+            Ok(Err(broadcast::error::RecvError::Closed)) => {
+                return Ok(StatusCode::NOT_MODIFIED.into_response())
+            }
+            Err(_elapsed) => return Ok(StatusCode::NOT_MODIFIED.into_response()),
+        }
     }
 }
 
 pub async fn handle_get_all_documents(
     State(state): State<SharedState>,
-) -> Result<Json<Vec<Document>>, StatusCode> {
-    let documents = state
-        .read()
-        .unwrap()
+) -> Result<Json<Vec<Document>>, ApiError> {
+    let documents = read_lock(&state)?
         .document_store
         .iter()
         .map(|(_, document)| document.clone())
@@ -64,14 +205,94 @@ pub async fn handle_get_all_documents(
     Ok(Json(documents))
 }
 
+/// Enqueues one indexing job per document, the same as
+/// `handle_create_document`, so a batch doesn't hold the write lock for
+/// N documents' worth of indexing. The caller polls `GET /tasks/{uid}`
+/// for each returned task to learn when its document becomes searchable.
+pub async fn handle_batch_create_documents(
+    State(state): State<SharedState>,
+    Json(inputs): Json<Vec<BatchDocumentInput>>,
+) -> Result<(StatusCode, Json<Vec<Task>>), ApiError> {
+    let documents: Vec<Document> = inputs
+        .into_iter()
+        .map(|input| {
+            require_non_empty_text(&input.text)?;
+            let id = input.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+            Ok(Document::new(id, input.text))
+        })
+        .collect::<Result<Vec<Document>, ApiError>>()?;
+
+    let mut state = write_lock(&state)?;
+    let tasks: Vec<Task> = documents
+        .into_iter()
+        .map(|document| {
+            let task = Task::new(TaskKind::CreateDocument);
+            state.task_store.insert(task.clone());
+            let _ = state.index_queue.send(IndexJob {
+                task_id: task.id.clone(),
+                document,
+            });
+            task
+        })
+        .collect();
+
+    Ok((StatusCode::ACCEPTED, Json(tasks)))
+}
+
+pub async fn handle_batch_get_documents(
+    State(state): State<SharedState>,
+    Json(ids): Json<Vec<String>>,
+) -> Result<Json<Vec<BatchGetResult>>, ApiError> {
+    let state = read_lock(&state)?;
+
+    let results = ids
+        .into_iter()
+        .map(|id| {
+            let document = state.document_store.get(&id).cloned();
+            BatchGetResult { id, document }
+        })
+        .collect();
+
+    Ok(Json(results))
+}
+
+pub async fn handle_batch_delete_documents(
+    State(state): State<SharedState>,
+    Json(ids): Json<Vec<String>>,
+) -> Result<Json<Vec<BatchDeleteResult>>, ApiError> {
+    let mut state = write_lock(&state)?;
+
+    // A storage error on one id must not discard the results already
+    // computed for the ids before it, so this doesn't short-circuit the
+    // whole batch the way collecting into a Result would.
+    let results = ids
+        .into_iter()
+        .map(|id| {
+            let deleted = state.document_store.remove(&id).unwrap_or(None).is_some();
+            BatchDeleteResult { id, deleted }
+        })
+        .collect();
+
+    Ok(Json(results))
+}
+
 pub fn document_routes() -> Router<SharedState> {
     Router::new()
         .route("/document", post(handle_create_document))
         .route(
             "/document/{key}",
-            get(handle_get_document).put(handle_update_document),
+            get(handle_get_document)
+                .put(handle_update_document)
+                .delete(handle_delete_document),
         )
+        .route("/document/{key}/poll", get(handle_poll_document))
         .route("/documents", get(handle_get_all_documents))
+        .route("/documents/batch", post(handle_batch_create_documents))
+        .route("/documents/batch/get", post(handle_batch_get_documents))
+        .route(
+            "/documents/batch/delete",
+            post(handle_batch_delete_documents),
+        )
 }
 
 #[cfg(test)]
@@ -84,20 +305,30 @@ mod test {
     use std::sync::{Arc, RwLock};
     use tower::ServiceExt;
 
+    use tokio::sync::mpsc;
+
     use super::*;
-    use crate::{app::AppState, documents::DocumentStore};
+    use crate::{app::AppState, documents::DocumentStore, tasks::TaskStore};
+
+    /// The index queue's receiver is dropped when this returns, so queued
+    /// jobs are never drained — fine for these handler-level tests, which
+    /// only assert on what the handler itself writes before indexing runs.
+    fn test_state(document_store: DocumentStore) -> Arc<RwLock<AppState>> {
+        let (index_queue, _jobs) = mpsc::unbounded_channel();
+        let (change_notifier, _changes) = broadcast::channel(16);
+        Arc::new(RwLock::new(AppState {
+            document_store,
+            task_store: TaskStore::default(),
+            index_queue,
+            change_notifier,
+        }))
+    }
 
     #[tokio::test]
     async fn create_document() {
-        // Create test state
-        let document_store = DocumentStore::default();
-        let app_state = AppState { document_store };
-        let shared_state = Arc::new(RwLock::new(app_state));
-
-        // Create app with state
+        let shared_state = test_state(DocumentStore::default());
         let app = document_routes().with_state(shared_state.clone());
 
-        // Build proper request
         let request = Request::builder()
             .method(Method::POST)
             .uri("/document")
@@ -105,27 +336,46 @@ mod test {
             .body(Body::from(json!({ "text": "hello world" }).to_string()))
             .unwrap();
 
-        // Call oneshot and await the result
         let response = app.oneshot(request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
 
-        // Verify document was stored
+        // Indexing itself is done by the background worker; the handler
+        // only needs to have enqueued a task for it.
         let state = shared_state.read().unwrap();
-        let documents: Vec<_> = state.document_store.iter().collect();
-        assert_eq!(documents.len(), 1);
-        assert_eq!(documents[0].1.text, "hello world");
-        assert_eq!(documents[0].1.word_count, 2);
+        assert_eq!(state.task_store.iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn create_document_rejects_empty_text() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state.clone());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/document")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "text": "   " }).to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "invalid_input");
+
+        assert_eq!(shared_state.read().unwrap().task_store.iter().count(), 0);
     }
 
     #[tokio::test]
     async fn get_document() {
-        // Create test state with a document
         let mut document_store = DocumentStore::default();
         let test_doc = Document::new("test-id".to_string(), "test content".to_string());
-        document_store.insert(test_doc.clone());
+        document_store.insert(test_doc.clone()).unwrap();
 
-        let app_state = AppState { document_store };
-        let shared_state = Arc::new(RwLock::new(app_state));
+        let shared_state = test_state(document_store);
         let app = document_routes().with_state(shared_state);
 
         let request = Request::builder()
@@ -140,9 +390,7 @@ mod test {
 
     #[tokio::test]
     async fn get_nonexistent_document() {
-        let document_store = DocumentStore::default();
-        let app_state = AppState { document_store };
-        let shared_state = Arc::new(RwLock::new(app_state));
+        let shared_state = test_state(DocumentStore::default());
         let app = document_routes().with_state(shared_state);
 
         let request = Request::builder()
@@ -157,13 +405,11 @@ mod test {
 
     #[tokio::test]
     async fn update_document() {
-        // Create test state with a document
         let mut document_store = DocumentStore::default();
         let test_doc = Document::new("test-id".to_string(), "test content".to_string());
-        document_store.insert(test_doc);
+        document_store.insert(test_doc).unwrap();
 
-        let app_state = AppState { document_store };
-        let shared_state = Arc::new(RwLock::new(app_state));
+        let shared_state = test_state(document_store);
         let app = document_routes().with_state(shared_state.clone());
 
         let request = Request::builder()
@@ -174,31 +420,57 @@ mod test {
             .unwrap();
 
         let response = app.oneshot(request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let state = shared_state.read().unwrap();
+        assert_eq!(state.task_store.iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn update_document_rejects_empty_text() {
+        let mut document_store = DocumentStore::default();
+        document_store
+            .insert(Document::new("test-id".to_string(), "test content".to_string()))
+            .unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = document_routes().with_state(shared_state.clone());
 
-        // Verify document was updated
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/document/test-id")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "text": "" }).to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // The existing document must be left untouched.
         let state = shared_state.read().unwrap();
-        let updated_doc = state.document_store.get("test-id").unwrap();
-        assert_eq!(updated_doc.text, "updated content");
-        assert_eq!(updated_doc.id, "test-id");
-        assert_eq!(updated_doc.word_count, 2);
+        assert_eq!(
+            state.document_store.get("test-id").unwrap().text,
+            "test content"
+        );
     }
 
     #[tokio::test]
     async fn get_all_documents() {
-        // Create test state with multiple documents
         let mut document_store = DocumentStore::default();
-        document_store.insert(Document::new(
-            "doc1".to_string(),
-            "first document".to_string(),
-        ));
-        document_store.insert(Document::new(
-            "doc2".to_string(),
-            "second document".to_string(),
-        ));
+        document_store
+            .insert(Document::new(
+                "doc1".to_string(),
+                "first document".to_string(),
+            ))
+            .unwrap();
+        document_store
+            .insert(Document::new(
+                "doc2".to_string(),
+                "second document".to_string(),
+            ))
+            .unwrap();
 
-        let app_state = AppState { document_store };
-        let shared_state = Arc::new(RwLock::new(app_state));
+        let shared_state = test_state(document_store);
         let app = document_routes().with_state(shared_state);
 
         let request = Request::builder()
@@ -210,4 +482,275 @@ mod test {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn delete_document() {
+        let mut document_store = DocumentStore::default();
+        document_store.insert(Document::new("doc1".to_string(), "to remove".to_string())).unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = document_routes().with_state(shared_state.clone());
+
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri("/document/doc1")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(shared_state.read().unwrap().document_store.get("doc1").is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_nonexistent_document() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri("/document/nonexistent")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn batch_create_documents() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state.clone());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/documents/batch")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!([{ "text": "first" }, { "id": "doc2", "text": "second" }]).to_string(),
+            ))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        // Indexing itself is done by the background worker; the handler
+        // only needs to have enqueued one task per document.
+        let state = shared_state.read().unwrap();
+        assert_eq!(state.task_store.iter().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn batch_create_documents_rejects_empty_text() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state.clone());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/documents/batch")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!([{ "text": "first" }, { "text": "  " }]).to_string(),
+            ))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // A rejected item must fail the whole batch rather than partially
+        // enqueueing the documents before it.
+        assert_eq!(shared_state.read().unwrap().task_store.iter().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn batch_get_documents() {
+        let mut document_store = DocumentStore::default();
+        document_store.insert(Document::new("doc1".to_string(), "present".to_string())).unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = document_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/documents/batch/get")
+            .header("content-type", "application/json")
+            .body(Body::from(json!(["doc1", "missing"]).to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let results: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(!results[0]["document"].is_null());
+        assert!(results[1]["document"].is_null());
+    }
+
+    #[tokio::test]
+    async fn batch_delete_documents() {
+        let mut document_store = DocumentStore::default();
+        document_store.insert(Document::new("doc1".to_string(), "to remove".to_string())).unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = document_routes().with_state(shared_state.clone());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/documents/batch/delete")
+            .header("content-type", "application/json")
+            .body(Body::from(json!(["doc1", "missing"]).to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(shared_state.read().unwrap().document_store.get("doc1").is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_delete_returns_a_result_per_id_even_when_storage_errors() {
+        use crate::storage::Storage;
+
+        let path = std::env::temp_dir().join(format!(
+            "search-engine-routes-test-{}.sqlite",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        std::fs::remove_file(path).ok();
+
+        let storage = Storage::open(path).unwrap();
+        let mut document_store = DocumentStore::with_storage(storage);
+        document_store
+            .insert(Document::new("doc1".to_string(), "first".to_string()))
+            .unwrap();
+        document_store
+            .insert(Document::new("doc2".to_string(), "second".to_string()))
+            .unwrap();
+
+        // Pull the table out from under the store's connection so every
+        // delete in the batch fails the way a real storage fault would.
+        rusqlite::Connection::open(path)
+            .unwrap()
+            .execute("DROP TABLE documents", [])
+            .unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = document_routes().with_state(shared_state.clone());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/documents/batch/delete")
+            .header("content-type", "application/json")
+            .body(Body::from(json!(["doc1", "doc2"]).to_string()))
+            .unwrap();
+
+        // A storage error partway through the batch must not abort the
+        // whole response and lose the results already computed for the
+        // ids before it — the handler should still return one result per
+        // id rather than a bare 500.
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let results: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["deleted"], false);
+        assert_eq!(results[1]["deleted"], false);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn poll_document_returns_as_soon_as_notified() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state.clone());
+
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            let mut state = shared_state.write().unwrap();
+            state
+                .document_store
+                .insert(Document::new("doc1".to_string(), "hello world".to_string()))
+                .unwrap();
+            let _ = state.change_notifier.send("doc1".to_string());
+        });
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/document/doc1/poll?timeout_ms=5000")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn poll_document_returns_immediately_if_already_present() {
+        let mut document_store = DocumentStore::default();
+        document_store
+            .insert(Document::new("doc1".to_string(), "already here".to_string()))
+            .unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = document_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/document/doc1/poll?timeout_ms=5000")
+            .body(Body::empty())
+            .unwrap();
+
+        // A document that already exists (and never changes again) must
+        // be returned right away rather than blocking for the full
+        // timeout.
+        let response = tokio::time::timeout(
+            std::time::Duration::from_millis(200),
+            app.oneshot(request),
+        )
+        .await
+        .expect("poll should have returned before the timeout")
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn poll_document_times_out_when_nothing_changes() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/document/doc1/poll?timeout_ms=20")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn poll_document_ignores_changes_to_other_keys() {
+        let shared_state = test_state(DocumentStore::default());
+        let app = document_routes().with_state(shared_state.clone());
+
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            let state = shared_state.read().unwrap();
+            let _ = state.change_notifier.send("other-doc".to_string());
+        });
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/document/doc1/poll?timeout_ms=50")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
 }
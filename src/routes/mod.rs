@@ -0,0 +1,7 @@
+mod documents;
+mod search;
+mod tasks;
+
+pub use documents::document_routes;
+pub use search::search_routes;
+pub use tasks::task_routes;
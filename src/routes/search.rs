@@ -0,0 +1,168 @@
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::app::SharedState;
+use crate::documents::{Document, SearchMode};
+use crate::error::{read_lock, ApiError};
+
+#[derive(Deserialize)]
+pub struct SearchParams {
+    q: String,
+    #[serde(default)]
+    mode: SearchMode,
+}
+
+#[derive(Serialize)]
+pub struct SearchHit {
+    document: Document,
+    score: f64,
+}
+
+pub async fn handle_search(
+    State(state): State<SharedState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<SearchHit>>, ApiError> {
+    let state = read_lock(&state)?;
+
+    let hits = state
+        .document_store
+        .search(&params.q, params.mode)
+        .into_iter()
+        .filter_map(|(id, score)| {
+            state
+                .document_store
+                .get(&id)
+                .map(|document| SearchHit {
+                    document: document.clone(),
+                    score,
+                })
+        })
+        .collect();
+
+    Ok(Json(hits))
+}
+
+pub fn search_routes() -> Router<SharedState> {
+    Router::new().route("/search", get(handle_search))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, RwLock};
+
+    use axum::body::Body;
+    use axum::http::{Method, Request, StatusCode};
+    use tokio::sync::{broadcast, mpsc};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::app::AppState;
+    use crate::documents::DocumentStore;
+    use crate::tasks::TaskStore;
+
+    fn test_state(document_store: DocumentStore) -> Arc<RwLock<AppState>> {
+        let (index_queue, _jobs) = mpsc::unbounded_channel();
+        let (change_notifier, _changes) = broadcast::channel(16);
+        Arc::new(RwLock::new(AppState {
+            document_store,
+            task_store: TaskStore::default(),
+            index_queue,
+            change_notifier,
+        }))
+    }
+
+    #[tokio::test]
+    async fn search_ranks_matching_documents() {
+        let mut document_store = DocumentStore::default();
+        document_store
+            .insert(Document::new(
+                "doc1".to_string(),
+                "the quick brown fox".to_string(),
+            ))
+            .unwrap();
+        document_store
+            .insert(Document::new(
+                "doc2".to_string(),
+                "the lazy dog sleeps".to_string(),
+            ))
+            .unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = search_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/search?q=fox")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let hits: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["document"]["id"], "doc1");
+        assert!(hits[0]["score"].as_f64().unwrap() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn search_fuzzy_mode_matches_typos() {
+        let mut document_store = DocumentStore::default();
+        document_store
+            .insert(Document::new(
+                "doc1".to_string(),
+                "the search engine indexes documents".to_string(),
+            ))
+            .unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = search_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/search?q=serach&mode=fuzzy")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let hits: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["document"]["id"], "doc1");
+    }
+
+    #[tokio::test]
+    async fn search_with_no_matches_returns_empty_hits() {
+        let mut document_store = DocumentStore::default();
+        document_store
+            .insert(Document::new("doc1".to_string(), "hello world".to_string()))
+            .unwrap();
+
+        let shared_state = test_state(document_store);
+        let app = search_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/search?q=nonexistent")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let hits: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert!(hits.is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+use axum::extract::{Path, State};
+use axum::routing::get;
+use axum::{Json, Router};
+
+use crate::app::SharedState;
+use crate::error::{read_lock, ApiError};
+use crate::tasks::Task;
+
+pub async fn handle_get_tasks(
+    State(state): State<SharedState>,
+) -> Result<Json<Vec<Task>>, ApiError> {
+    let tasks = read_lock(&state)?
+        .task_store
+        .iter()
+        .map(|(_, task)| task.clone())
+        .collect();
+
+    Ok(Json(tasks))
+}
+
+pub async fn handle_get_task(
+    Path(uid): Path<String>,
+    State(state): State<SharedState>,
+) -> Result<Json<Task>, ApiError> {
+    match read_lock(&state)?.task_store.get(&uid) {
+        Some(task) => Ok(Json(task.clone())),
+        None => Err(ApiError::TaskNotFound),
+    }
+}
+
+pub fn task_routes() -> Router<SharedState> {
+    Router::new()
+        .route("/tasks", get(handle_get_tasks))
+        .route("/tasks/{uid}", get(handle_get_task))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, RwLock};
+
+    use axum::body::Body;
+    use axum::http::{Method, Request, StatusCode};
+    use tokio::sync::{broadcast, mpsc};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::app::AppState;
+    use crate::documents::DocumentStore;
+    use crate::tasks::{TaskKind, TaskStore};
+
+    fn test_state(task_store: TaskStore) -> Arc<RwLock<AppState>> {
+        let (index_queue, _jobs) = mpsc::unbounded_channel();
+        let (change_notifier, _changes) = broadcast::channel(16);
+        Arc::new(RwLock::new(AppState {
+            document_store: DocumentStore::default(),
+            task_store,
+            index_queue,
+            change_notifier,
+        }))
+    }
+
+    #[tokio::test]
+    async fn get_tasks_lists_every_task() {
+        let mut task_store = TaskStore::default();
+        task_store.insert(Task::new(TaskKind::CreateDocument));
+        task_store.insert(Task::new(TaskKind::UpdateDocument));
+
+        let shared_state = test_state(task_store);
+        let app = task_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/tasks")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let tasks: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(tasks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_task_returns_matching_task() {
+        let task = Task::new(TaskKind::CreateDocument);
+        let task_id = task.id.clone();
+
+        let mut task_store = TaskStore::default();
+        task_store.insert(task);
+
+        let shared_state = test_state(task_store);
+        let app = task_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/tasks/{task_id}"))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let returned: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(returned["id"], task_id);
+    }
+
+    #[tokio::test]
+    async fn get_task_returns_not_found_for_unknown_id() {
+        let shared_state = test_state(TaskStore::default());
+        let app = task_routes().with_state(shared_state);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/tasks/nonexistent")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection};
+
+use crate::documents::Document;
+
+/// Write-through durable storage for documents, backed by SQLite.
+/// `DocumentStore` keeps an in-memory cache (and the inverted indexes) for
+/// fast reads; this is only responsible for making that cache survive a
+/// restart.
+///
+/// The connection is behind a `Mutex` rather than held bare: `rusqlite`'s
+/// `Connection` uses interior mutability (a `RefCell`-based statement
+/// cache) that's `Send` but not `Sync`, and `Storage` is reached through
+/// `AppState`'s shared `RwLock`, so it needs to be safely usable from
+/// whichever handler thread takes the lock.
+pub struct Storage {
+    connection: Mutex<Connection>,
+}
+
+impl Storage {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS documents (
+                id   TEXT PRIMARY KEY,
+                text TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Storage {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn load_all(&self) -> rusqlite::Result<Vec<Document>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare("SELECT id, text FROM documents")?;
+        let rows = statement.query_map([], |row| {
+            let id: String = row.get(0)?;
+            let text: String = row.get(1)?;
+            Ok(Document::new(id, text))
+        })?;
+        rows.collect()
+    }
+
+    pub fn put(&self, document: &Document) -> rusqlite::Result<()> {
+        self.connection.lock().unwrap().execute(
+            "INSERT INTO documents (id, text) VALUES (?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET text = excluded.text",
+            params![document.id, document.text],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete(&self, id: &str) -> rusqlite::Result<()> {
+        self.connection
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM documents WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_and_load_all() {
+        let storage = Storage::open(":memory:").unwrap();
+        storage
+            .put(&Document::new("doc1".to_string(), "hello world".to_string()))
+            .unwrap();
+
+        let loaded = storage.load_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, "doc1");
+        assert_eq!(loaded[0].text, "hello world");
+    }
+
+    #[test]
+    fn test_put_overwrites_existing_id() {
+        let storage = Storage::open(":memory:").unwrap();
+        storage
+            .put(&Document::new("doc1".to_string(), "first".to_string()))
+            .unwrap();
+        storage
+            .put(&Document::new("doc1".to_string(), "second".to_string()))
+            .unwrap();
+
+        let loaded = storage.load_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].text, "second");
+    }
+
+    #[test]
+    fn test_delete_removes_document() {
+        let storage = Storage::open(":memory:").unwrap();
+        storage
+            .put(&Document::new("doc1".to_string(), "hello".to_string()))
+            .unwrap();
+        storage.delete("doc1").unwrap();
+
+        assert!(storage.load_all().unwrap().is_empty());
+    }
+}
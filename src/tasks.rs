@@ -0,0 +1,117 @@
+use std::collections::{hash_map::Iter, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::documents::Document;
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    CreateDocument,
+    UpdateDocument,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Enqueued,
+    Processing,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Task {
+    pub id: String,
+    pub kind: TaskKind,
+    pub status: TaskStatus,
+    pub enqueued_at_ms: u128,
+    pub error: Option<String>,
+}
+
+impl Task {
+    pub fn new(kind: TaskKind) -> Self {
+        Task {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            status: TaskStatus::Enqueued,
+            enqueued_at_ms: now_millis(),
+            error: None,
+        }
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// A unit of indexing work handed off to the background worker: the
+/// document to index and the task tracking it, so callers can poll for
+/// completion instead of waiting on it synchronously.
+pub struct IndexJob {
+    pub task_id: String,
+    pub document: Document,
+}
+
+#[derive(Default)]
+pub struct TaskStore {
+    tasks: HashMap<String, Task>,
+}
+
+impl TaskStore {
+    pub fn insert(&mut self, task: Task) {
+        self.tasks.insert(task.id.clone(), task);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Task> {
+        self.tasks.get(id)
+    }
+
+    pub fn iter(&self) -> Iter<String, Task> {
+        self.tasks.iter()
+    }
+
+    pub fn set_status(&mut self, id: &str, status: TaskStatus, error: Option<String>) {
+        if let Some(task) = self.tasks.get_mut(id) {
+            task.status = status;
+            task.error = error;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_starts_enqueued() {
+        let task = Task::new(TaskKind::CreateDocument);
+        assert_eq!(task.status, TaskStatus::Enqueued);
+        assert!(task.error.is_none());
+    }
+
+    #[test]
+    fn test_task_store_set_status() {
+        let mut store = TaskStore::default();
+        let task = Task::new(TaskKind::UpdateDocument);
+        let id = task.id.clone();
+        store.insert(task);
+
+        store.set_status(&id, TaskStatus::Failed, Some("boom".to_string()));
+
+        let updated = store.get(&id).unwrap();
+        assert_eq!(updated.status, TaskStatus::Failed);
+        assert_eq!(updated.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_task_store_get_nonexistent() {
+        let store = TaskStore::default();
+        assert!(store.get("nonexistent").is_none());
+    }
+}